@@ -0,0 +1,166 @@
+// Long-running entropy daemon: keeps the capture loop running in the
+// background and serves conditioned entropy to clients over a socket,
+// analogous to a media server continuously publishing a live stream to
+// subscribers rather than a CLI that captures a batch and exits.
+//
+// Wire protocol, per connection: the client sends a 4-byte big-endian u32
+// requesting N bytes, and the server replies with exactly N bytes once the
+// pool has accumulated that much entropy. A connection can make any number
+// of such requests in sequence.
+
+use ocelli::capture::{CaptureSource, NetworkCaptureSource, V4lCaptureSource};
+use ocelli::{BitOrder, Ocelli};
+use std::collections::VecDeque;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+
+struct EntropyPool {
+    bytes: Mutex<VecDeque<u8>>,
+    ready: Condvar,
+}
+
+impl EntropyPool {
+    fn new() -> Self {
+        EntropyPool {
+            bytes: Mutex::new(VecDeque::new()),
+            ready: Condvar::new(),
+        }
+    }
+
+    fn push(&self, entropy: &[u8]) {
+        let mut bytes = self.bytes.lock().unwrap();
+        bytes.extend(entropy.iter().copied());
+        self.ready.notify_all();
+    }
+
+    /// Blocks until at least `n` bytes are available, then drains and
+    /// returns exactly `n` of them. This is where a slow-filling pool
+    /// applies backpressure to a client asking for more than is on hand.
+    fn take(&self, n: usize) -> Vec<u8> {
+        let mut bytes = self.bytes.lock().unwrap();
+        bytes = self.ready.wait_while(bytes, |b| b.len() < n).unwrap();
+        bytes.drain(..n).collect()
+    }
+}
+
+fn capture_loop(mut source: Box<dyn CaptureSource>, quick: bool, pool: Arc<EntropyPool>) {
+    let ocelli = Ocelli;
+    let shannon_threshold = if quick { 7.9 } else { 4.5 };
+    let (width, _height) = source.dimensions();
+
+    let mut previous_frame = match source.next_grayscale_frame() {
+        Ok(frame) => frame,
+        Err(e) => {
+            eprintln!("Capture failed: {e}");
+            return;
+        }
+    };
+    let mut frame_count = 0usize;
+
+    loop {
+        let current_frame = match source.next_grayscale_frame() {
+            Ok(frame) => frame,
+            Err(e) => {
+                eprintln!("Capture failed: {e}");
+                return;
+            }
+        };
+
+        let entropy = if quick {
+            ocelli.pick_and_flip(&current_frame, 0, 255, frame_count, BitOrder::Msb)
+        } else if ocelli.is_covered(&current_frame, 50) {
+            ocelli
+                .chop_and_tack(&current_frame, &previous_frame, width, 20, BitOrder::Msb)
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let entropy = ocelli.whiten(&entropy, BitOrder::Msb);
+
+        previous_frame = current_frame;
+        frame_count += 1;
+
+        if entropy.is_empty() {
+            continue;
+        }
+
+        let shannon_entropy = ocelli.shannon(&entropy);
+        if shannon_entropy >= shannon_threshold {
+            pool.push(&entropy);
+        }
+    }
+}
+
+fn handle_client(mut stream: impl Read + Write, pool: Arc<EntropyPool>) {
+    loop {
+        let mut len_buf = [0u8; 4];
+        if stream.read_exact(&mut len_buf).is_err() {
+            return; // client disconnected
+        }
+        let requested = u32::from_be_bytes(len_buf) as usize;
+
+        let bytes = pool.take(requested);
+        if stream.write_all(&bytes).is_err() {
+            return;
+        }
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 3 {
+        eprintln!(
+            "Usage: {} <camera index | rtsp/http url> <tcp addr:port | unix:/path/to.sock> [-q]",
+            args[0]
+        );
+        std::process::exit(1);
+    }
+
+    let source_arg = &args[1];
+    let bind_arg = &args[2];
+    let quick = args.contains(&String::from("-q"));
+
+    let source: Box<dyn CaptureSource> = if source_arg.contains("://") {
+        Box::new(NetworkCaptureSource::new(source_arg)?)
+    } else {
+        let camera_index: usize = source_arg.parse().expect("Failed to parse camera index as a number");
+        Box::new(V4lCaptureSource::new(camera_index, 1280, 720)?)
+    };
+
+    let pool = Arc::new(EntropyPool::new());
+    let capture_pool = Arc::clone(&pool);
+    thread::spawn(move || capture_loop(source, quick, capture_pool));
+
+    if let Some(path) = bind_arg.strip_prefix("unix:") {
+        #[cfg(unix)]
+        {
+            let _ = std::fs::remove_file(path);
+            let listener = UnixListener::bind(path)?;
+            println!("Entropy daemon listening on unix:{path}");
+            for conn in listener.incoming() {
+                let stream = conn?;
+                let conn_pool = Arc::clone(&pool);
+                thread::spawn(move || handle_client(stream, conn_pool));
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            return Err("unix sockets are only supported on unix platforms".into());
+        }
+    } else {
+        let listener = TcpListener::bind(bind_arg)?;
+        println!("Entropy daemon listening on {bind_arg}");
+        for conn in listener.incoming() {
+            let stream = conn?;
+            let conn_pool = Arc::clone(&pool);
+            thread::spawn(move || handle_client(stream, conn_pool));
+        }
+    }
+
+    Ok(())
+}
@@ -1,101 +1,84 @@
-use ocelli::Ocelli;
-use v4l::prelude::*;
-use v4l::video::Capture;
-use v4l::buffer::Type;
-use v4l::format::FourCC;
-use v4l::io::traits::CaptureStream;
-use image::ImageReader;
+use ocelli::capture::{CaptureSource, NetworkCaptureSource, V4lCaptureSource};
+use ocelli::container::{self, Method};
+use ocelli::{BitOrder, Ocelli};
 use std::fs::File;
 use std::io::{stdin, Write};
 use chrono::Local;
 use std::time::Instant;
 
-fn frame_to_grayscale(data: &[u8]) -> Vec<u8> {
-    let img = ImageReader::new(std::io::Cursor::new(data))
-        .with_guessed_format()
-        .expect("Failed to guess format")
-        .decode()
-        .expect("Failed to decode image");
-    let gray = img.into_luma8(); // Convert to grayscale
-    gray.into_raw() // Return raw pixel data as Vec<u8>
+fn open_v4l_source(camera_index: usize) -> Result<V4lCaptureSource, Box<dyn std::error::Error>> {
+    match V4lCaptureSource::new(camera_index, 1920, 1080) {
+        Ok(source) => Ok(source),
+        Err(_) => {
+            println!("Failed to set resolution to 1920x1080. Falling back to 1280x720.");
+            Ok(V4lCaptureSource::new(camera_index, 1280, 720)?)
+        }
+    }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args: Vec<String> = std::env::args().collect();
     if args.len() < 3 {
-        eprintln!("Usage: {} <camera index> <entropy length in bytes>", args[0]);
+        eprintln!("Usage: {} <camera index | rtsp/http url> <entropy length in bytes>", args[0]);
         std::process::exit(1);
     }
 
     // Check if the quick flag is set
     let quick = args.contains(&String::from("-q"));
 
-    let camera_index: usize = args[1].parse().expect("Failed to parse camera index as a number");
+    let source_arg = &args[1];
     let length: usize = args[2].parse().expect("Failed to parse entropy length as a number");
 
-    let dev = Device::new(camera_index).expect("Failed to open camera");
-
-    // Set the desired format and resolution
-    let mut format = dev.format().expect("Failed to get camera format");
-    format.fourcc = FourCC::new(b"MJPG"); // Use MJPG for higher resolutions
-    format.width = 1920;
-    format.height = 1080;
-
-    if let Err(_) = dev.set_format(&format) {
-        println!("Failed to set resolution to 1920x1080. Falling back to 1280x720.");
-        format.width = 1280;
-        format.height = 720;
-        dev.set_format(&format)
-            .expect("Failed to set resolution to 1280x720");
-    }
-
-    println!(
-        "Using resolution: {}x{} (FourCC: {})",
-        format.width, format.height, format.fourcc
-    );
+    let mut source: Box<dyn CaptureSource> = if source_arg.contains("://") {
+        Box::new(NetworkCaptureSource::new(source_arg)?)
+    } else {
+        let camera_index: usize = source_arg.parse().expect("Failed to parse camera index as a number");
+        Box::new(open_v4l_source(camera_index)?)
+    };
 
-    let mut stream = MmapStream::with_buffers(&dev, Type::VideoCapture, 4)
-        .expect("Failed to create stream");
+    let (width, height) = source.dimensions();
+    println!("Using resolution: {}x{}", width, height);
 
     let ocelli = Ocelli;
     let mut total_entropy = Vec::new();
+    let mut chunk_shannon = Vec::new();
     let start_time = Instant::now();
     let shannon_threshold = 7.9;
     let mut frame_count = 0;
 
     while total_entropy.len() < length {
         // Capture first frame
-        let (data1, _) = stream.next().expect("Failed to capture frame");
+        let grayscale_data1 = source.next_grayscale_frame()?;
 
         // Skip the first 30 frames
         if frame_count <= 30 {
             frame_count += 1;
         } else {
-
-            let grayscale_data1 = frame_to_grayscale(&data1);
-
             let mut entropy: Vec<u8> = [0].to_vec();
-            
+
             if quick {
                 // Quicker capture using Pick and Flip
-                entropy = ocelli.whiten(&ocelli.pick_and_flip(&grayscale_data1, frame_count as usize));
+                entropy = ocelli.whiten(
+                    &ocelli.pick_and_flip(&grayscale_data1, 0, 255, frame_count as usize, BitOrder::Msb),
+                    BitOrder::Msb,
+                );
+            } else if ocelli.is_covered(&grayscale_data1, 50) {
+                // Capture second frame
+                let grayscale_data2 = source.next_grayscale_frame()?;
+
+                // Generate entropy using chop_and_tack
+                entropy = ocelli
+                    .chop_and_tack(&grayscale_data1, &grayscale_data2, width, 30, BitOrder::Msb)
+                    .unwrap_or_default();
             } else {
-                if ocelli.is_covered(&grayscale_data1, 50) {
-                    // Capture second frame
-                    let (data2, _) = stream.next().expect("Failed to capture second frame");
-                    let grayscale_data2 = frame_to_grayscale(&data2);
-
-                    // Generate entropy using chop_and_tack
-                    entropy = ocelli.chop_and_tack(&grayscale_data1, &grayscale_data2, format.width as usize, 30);
-                } else {
-                    println!("Camera is not covered. Please cover the camera.");
-                    frame_count = 0;
-                }
+                println!("Camera is not covered. Please cover the camera.");
+                frame_count = 0;
             }
 
             let shannon_entropy = ocelli.shannon(&entropy);
 
             if shannon_entropy >= shannon_threshold {
+                chunk_shannon.push(shannon_entropy);
                 total_entropy.extend(entropy);
                 println!(
                     "Collected {} of {} bytes of entropy (Shannon entropy: {:.3})",
@@ -106,7 +89,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             } else {
                 println!("Rejected entropy for frame {} (Shannon entropy: {:.3})", frame_count, shannon_entropy);
             }
-            
         }
     }
 
@@ -131,10 +113,22 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     if input.trim().eq_ignore_ascii_case("file") {
         let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
-        let filename = format!("entropy_{}.bin", timestamp);
+        let filename = format!("entropy_{}.ocel", timestamp);
+
+        let method = if quick { Method::PickAndFlip } else { Method::ChopAndTack };
+        let meta = container::Meta::now(
+            method,
+            width as u32,
+            height as u32,
+            if quick { 0 } else { 30 },
+            quick, // only the pick-and-flip branch whitens
+            chunk_shannon,
+            final_shannon_entropy,
+        );
+        let container_bytes = container::write_container(&meta, &total_entropy);
 
         let mut file = File::create(&filename).expect("Failed to create file");
-        file.write_all(&total_entropy).expect("Failed to write data to file");
+        file.write_all(&container_bytes).expect("Failed to write data to file");
 
         println!("Entropy saved to file: {}", filename);
     } else if input.trim().eq_ignore_ascii_case("print") {
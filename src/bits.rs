@@ -0,0 +1,93 @@
+// Bit-level pack/unpack utilities shared by the extractors, with a
+// selectable MSB/LSB folding order.
+
+/// Order in which bits are folded into (or read out of) a byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitOrder {
+    /// The first bit becomes the most significant bit of the byte.
+    Msb,
+    /// The first bit becomes the least significant bit of the byte.
+    Lsb,
+}
+
+/// Packs a slice of 0/1 bits into bytes in `order`. A trailing run shorter
+/// than 8 bits is zero-padded up to a full byte rather than being dropped.
+pub fn pack_bits(bits: &[u8], order: BitOrder) -> Vec<u8> {
+    bits.chunks(8)
+        .map(|chunk| {
+            let mut byte = 0u8;
+            for (i, &bit) in chunk.iter().enumerate() {
+                match order {
+                    BitOrder::Msb => byte |= (bit & 1) << (7 - i),
+                    BitOrder::Lsb => byte |= (bit & 1) << i,
+                }
+            }
+            byte
+        })
+        .collect()
+}
+
+/// Packs a slice of 0/1 bits into bytes in `order`, dropping a trailing run
+/// shorter than 8 bits instead of zero-padding it. Use this instead of
+/// `pack_bits` when the caller can't tolerate fixed padding bits ending up
+/// in the output, e.g. raw or whitened entropy.
+pub fn pack_bits_truncating(bits: &[u8], order: BitOrder) -> Vec<u8> {
+    let whole_bytes = bits.len() - (bits.len() % 8);
+    pack_bits(&bits[..whole_bytes], order)
+}
+
+/// Unpacks bytes into a vector of 0/1 bits, one entry per bit, in `order`.
+pub fn unpack_bits(bytes: &[u8], order: BitOrder) -> Vec<u8> {
+    let mut bits = Vec::with_capacity(bytes.len() * 8);
+    for &byte in bytes {
+        for i in 0..8 {
+            let bit = match order {
+                BitOrder::Msb => (byte >> (7 - i)) & 1,
+                BitOrder::Lsb => (byte >> i) & 1,
+            };
+            bits.push(bit);
+        }
+    }
+    bits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_msb_matches_old_fold() {
+        // (byte << 1) | bit, the old `bits_to_bytes` behavior, is MSB-first.
+        let bits = [1, 0, 1, 0, 1, 0, 1, 0];
+        assert_eq!(pack_bits(&bits, BitOrder::Msb), vec![0b1010_1010]);
+    }
+
+    #[test]
+    fn pack_lsb_reverses_bit_order_within_a_byte() {
+        let bits = [1, 0, 1, 0, 1, 0, 1, 0];
+        assert_eq!(pack_bits(&bits, BitOrder::Lsb), vec![0b0101_0101]);
+    }
+
+    #[test]
+    fn pack_zero_pads_a_trailing_partial_byte() {
+        let bits = [1; 11];
+        let packed = pack_bits(&bits, BitOrder::Msb);
+        assert_eq!(packed, vec![0b1111_1111, 0b1110_0000]);
+    }
+
+    #[test]
+    fn pack_truncating_drops_a_trailing_partial_byte() {
+        let bits = [1; 11];
+        let packed = pack_bits_truncating(&bits, BitOrder::Msb);
+        assert_eq!(packed, vec![0b1111_1111]);
+    }
+
+    #[test]
+    fn pack_and_unpack_round_trip() {
+        for order in [BitOrder::Msb, BitOrder::Lsb] {
+            let bytes = vec![0x5A, 0xC3, 0x00, 0xFF];
+            let bits = unpack_bits(&bytes, order);
+            assert_eq!(pack_bits(&bits, order), bytes);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+// Capture backends for the entropy pipeline, behind the `CaptureSource`
+// trait so new sources can be added without touching the extractors.
+
+use std::fmt;
+
+/// Error returned by a `CaptureSource` when a frame can't be produced.
+#[derive(Debug)]
+pub struct CaptureError(String);
+
+impl CaptureError {
+    pub fn new(msg: impl Into<String>) -> Self {
+        CaptureError(msg.into())
+    }
+}
+
+impl fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for CaptureError {}
+
+impl From<std::io::Error> for CaptureError {
+    fn from(e: std::io::Error) -> Self {
+        CaptureError(e.to_string())
+    }
+}
+
+impl From<opencv::Error> for CaptureError {
+    fn from(e: opencv::Error) -> Self {
+        CaptureError(e.to_string())
+    }
+}
+
+pub type CaptureResult<T> = Result<T, CaptureError>;
+
+/// A source of grayscale frames for the entropy pipeline, decoupled from
+/// whatever physical or network device is behind it.
+pub trait CaptureSource {
+    /// Blocks until the next grayscale frame is available.
+    fn next_grayscale_frame(&mut self) -> CaptureResult<Vec<u8>>;
+
+    /// The `(width, height)` of the frames this source produces.
+    fn dimensions(&self) -> (usize, usize);
+}
+
+fn frame_to_grayscale(data: &[u8]) -> CaptureResult<Vec<u8>> {
+    let img = image::ImageReader::new(std::io::Cursor::new(data))
+        .with_guessed_format()
+        .map_err(|e| CaptureError::new(format!("failed to guess image format: {e}")))?
+        .decode()
+        .map_err(|e| CaptureError::new(format!("failed to decode frame: {e}")))?;
+    Ok(img.into_luma8().into_raw())
+}
+
+/// Captures MJPEG frames from a local V4L2 device via `v4l`.
+pub struct V4lCaptureSource {
+    // `stream` borrows from `device` for its lifetime (the device is boxed so
+    // its address is stable, and the borrow below is widened to `'static`
+    // accordingly). Struct fields are dropped in declaration order, so
+    // `stream` MUST be declared before `device`: otherwise `device` would be
+    // freed first, leaving `stream`'s internal `&Device` dangling for its own
+    // drop (which performs ioctl teardown against it). Do not reorder these.
+    stream: v4l::io::mmap::Stream<'static>,
+    device: Box<v4l::Device>,
+    width: usize,
+    height: usize,
+}
+
+impl V4lCaptureSource {
+    pub fn new(camera_index: usize, width: u32, height: u32) -> CaptureResult<Self> {
+        let device = Box::new(v4l::Device::new(camera_index)?);
+
+        let mut format = device.format()?;
+        format.fourcc = v4l::format::FourCC::new(b"MJPG");
+        format.width = width;
+        format.height = height;
+        device.set_format(&format)?;
+
+        let device_ref: &'static v4l::Device = unsafe { &*(device.as_ref() as *const v4l::Device) };
+        let stream = v4l::io::mmap::Stream::with_buffers(device_ref, v4l::buffer::Type::VideoCapture, 4)?;
+
+        Ok(V4lCaptureSource {
+            stream,
+            device,
+            width: format.width as usize,
+            height: format.height as usize,
+        })
+    }
+}
+
+impl CaptureSource for V4lCaptureSource {
+    fn next_grayscale_frame(&mut self) -> CaptureResult<Vec<u8>> {
+        use v4l::io::traits::CaptureStream;
+        let (data, _) = self.stream.next()?;
+        frame_to_grayscale(data)
+    }
+
+    fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+}
+
+fn open_opencv_stream(path_or_index: opencv::videoio::VideoCapture) -> CaptureResult<(opencv::videoio::VideoCapture, usize, usize)> {
+    use opencv::prelude::*;
+    let mut cam = path_or_index;
+    if !cam.is_opened()? {
+        return Err(CaptureError::new("failed to open camera stream"));
+    }
+
+    let mut frame = opencv::core::Mat::default();
+    cam.read(&mut frame)?;
+    if frame.empty() {
+        return Err(CaptureError::new("failed to capture initial frame"));
+    }
+
+    let width = frame.cols() as usize;
+    let height = frame.rows() as usize;
+    Ok((cam, width, height))
+}
+
+fn read_opencv_grayscale(cam: &mut opencv::videoio::VideoCapture) -> CaptureResult<Vec<u8>> {
+    use opencv::prelude::*;
+    let mut frame = opencv::core::Mat::default();
+    cam.read(&mut frame)?;
+    let mut gray = opencv::core::Mat::default();
+    opencv::imgproc::cvt_color(&frame, &mut gray, opencv::imgproc::COLOR_BGR2GRAY, 0)?;
+    Ok(gray.data_bytes()?.to_vec())
+}
+
+/// Captures frames from a local device via OpenCV's `VideoCapture`.
+pub struct OpenCvCaptureSource {
+    cam: opencv::videoio::VideoCapture,
+    width: usize,
+    height: usize,
+}
+
+impl OpenCvCaptureSource {
+    pub fn new(camera_index: i32) -> CaptureResult<Self> {
+        let cam = opencv::videoio::VideoCapture::new(camera_index, opencv::videoio::CAP_V4L)?;
+        let (cam, width, height) = open_opencv_stream(cam)?;
+        Ok(OpenCvCaptureSource { cam, width, height })
+    }
+}
+
+impl CaptureSource for OpenCvCaptureSource {
+    fn next_grayscale_frame(&mut self) -> CaptureResult<Vec<u8>> {
+        read_opencv_grayscale(&mut self.cam)
+    }
+
+    fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+}
+
+/// Captures frames from a remote RTSP/HTTP camera feed (e.g. an IP camera, or
+/// a media server re-publishing one), opened through OpenCV's FFmpeg backend
+/// the same way a local device is opened, just pointed at a URL instead of a
+/// device index.
+pub struct NetworkCaptureSource {
+    cam: opencv::videoio::VideoCapture,
+    width: usize,
+    height: usize,
+}
+
+impl NetworkCaptureSource {
+    pub fn new(url: &str) -> CaptureResult<Self> {
+        let cam = opencv::videoio::VideoCapture::from_file(url, opencv::videoio::CAP_FFMPEG)?;
+        let (cam, width, height) = open_opencv_stream(cam)?;
+        Ok(NetworkCaptureSource { cam, width, height })
+    }
+}
+
+impl CaptureSource for NetworkCaptureSource {
+    fn next_grayscale_frame(&mut self) -> CaptureResult<Vec<u8>> {
+        read_opencv_grayscale(&mut self.cam)
+    }
+
+    fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+}
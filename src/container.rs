@@ -0,0 +1,299 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Self-describing entropy container, built from nested length-prefixed boxes
+// in the style of ISO-BMFF: a 4-byte size, a 4-byte ASCII tag, then content.
+// The size is backpatched in once the content is known.
+
+const TAG_ROOT: [u8; 4] = *b"OCEL";
+const TAG_META: [u8; 4] = *b"META";
+const TAG_HLTH: [u8; 4] = *b"HLTH";
+const TAG_DATA: [u8; 4] = *b"DATA";
+
+/// Which extractor produced a batch of entropy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    ChopAndTack,
+    PickAndFlip,
+}
+
+impl Method {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Method::ChopAndTack => "chop_and_tack",
+            Method::PickAndFlip => "pick_and_flip",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "chop_and_tack" => Some(Method::ChopAndTack),
+            "pick_and_flip" => Some(Method::PickAndFlip),
+            _ => None,
+        }
+    }
+}
+
+/// Provenance recorded alongside a batch of conditioned entropy: which method
+/// produced it, the source resolution, whether it was whitened, and the
+/// Shannon scores that got it past the reject threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Meta {
+    pub method: Method,
+    pub width: u32,
+    pub height: u32,
+    pub minimum_distance: u32,
+    pub whitened: bool,
+    pub chunk_shannon: Vec<f64>,
+    pub final_shannon: f64,
+    pub timestamp: u64,
+}
+
+impl Meta {
+    /// Builds a `Meta` stamped with the current Unix time, in seconds.
+    pub fn now(
+        method: Method,
+        width: u32,
+        height: u32,
+        minimum_distance: u32,
+        whitened: bool,
+        chunk_shannon: Vec<f64>,
+        final_shannon: f64,
+    ) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Meta {
+            method,
+            width,
+            height,
+            minimum_distance,
+            whitened,
+            chunk_shannon,
+            final_shannon,
+            timestamp,
+        }
+    }
+}
+
+/// The fully parsed contents of an `OCEL` container.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Container {
+    pub meta: Meta,
+    pub data: Vec<u8>,
+}
+
+fn write_box(buf: &mut Vec<u8>, tag: [u8; 4], content: impl FnOnce(&mut Vec<u8>)) {
+    let size_pos = buf.len();
+    buf.extend_from_slice(&[0u8; 4]); // placeholder, backpatched below
+    buf.extend_from_slice(&tag);
+    content(buf);
+    let size = (buf.len() - size_pos) as u32;
+    buf[size_pos..size_pos + 4].copy_from_slice(&size.to_be_bytes());
+}
+
+fn write_str(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(&(s.len() as u32).to_be_bytes());
+    buf.extend_from_slice(s.as_bytes());
+}
+
+fn write_meta(buf: &mut Vec<u8>, meta: &Meta) {
+    write_str(buf, meta.method.as_str());
+    buf.extend_from_slice(&meta.width.to_be_bytes());
+    buf.extend_from_slice(&meta.height.to_be_bytes());
+    buf.extend_from_slice(&meta.minimum_distance.to_be_bytes());
+    buf.push(meta.whitened as u8);
+    buf.extend_from_slice(&meta.final_shannon.to_be_bytes());
+    buf.extend_from_slice(&meta.timestamp.to_be_bytes());
+}
+
+fn write_hlth(buf: &mut Vec<u8>, chunk_shannon: &[f64]) {
+    buf.extend_from_slice(&(chunk_shannon.len() as u32).to_be_bytes());
+    for score in chunk_shannon {
+        buf.extend_from_slice(&score.to_be_bytes());
+    }
+}
+
+/// Serializes `meta` and `data` into the `OCEL` container format.
+pub fn write_container(meta: &Meta, data: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_box(&mut buf, TAG_ROOT, |buf| {
+        write_box(buf, TAG_META, |buf| write_meta(buf, meta));
+        write_box(buf, TAG_HLTH, |buf| write_hlth(buf, &meta.chunk_shannon));
+        write_box(buf, TAG_DATA, |buf| buf.extend_from_slice(data));
+    });
+    buf
+}
+
+/// One box at whatever nesting level `iter_boxes` was called on: its type tag
+/// and content slice, not including the 8-byte header.
+struct BoxView<'a> {
+    tag: [u8; 4],
+    content: &'a [u8],
+}
+
+/// Walks sibling boxes in `buf`, reading size+tag and slicing the content.
+/// Stops (rather than erroring) on truncated or malformed input, since a
+/// partial container is not recoverable.
+fn iter_boxes(buf: &[u8]) -> impl Iterator<Item = BoxView<'_>> {
+    struct Boxes<'a> {
+        rest: &'a [u8],
+    }
+
+    impl<'a> Iterator for Boxes<'a> {
+        type Item = BoxView<'a>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.rest.len() < 8 {
+                return None;
+            }
+            let size = u32::from_be_bytes(self.rest[0..4].try_into().unwrap()) as usize;
+            if size < 8 || size > self.rest.len() {
+                return None;
+            }
+            let mut tag = [0u8; 4];
+            tag.copy_from_slice(&self.rest[4..8]);
+            let content = &self.rest[8..size];
+            self.rest = &self.rest[size..];
+            Some(BoxView { tag, content })
+        }
+    }
+
+    Boxes { rest: buf }
+}
+
+fn read_meta(content: &[u8]) -> Option<(Method, u32, u32, u32, bool, f64, u64)> {
+    if content.len() < 4 {
+        return None;
+    }
+    let name_len = u32::from_be_bytes(content[0..4].try_into().unwrap()) as usize;
+    let mut pos = 4usize.checked_add(name_len)?;
+    let method = Method::from_str(std::str::from_utf8(content.get(4..pos)?).ok()?)?;
+
+    let width = u32::from_be_bytes(content.get(pos..pos + 4)?.try_into().unwrap());
+    pos += 4;
+    let height = u32::from_be_bytes(content.get(pos..pos + 4)?.try_into().unwrap());
+    pos += 4;
+    let minimum_distance = u32::from_be_bytes(content.get(pos..pos + 4)?.try_into().unwrap());
+    pos += 4;
+    let whitened = *content.get(pos)? != 0;
+    pos += 1;
+    let final_shannon = f64::from_be_bytes(content.get(pos..pos + 8)?.try_into().unwrap());
+    pos += 8;
+    let timestamp = u64::from_be_bytes(content.get(pos..pos + 8)?.try_into().unwrap());
+
+    Some((method, width, height, minimum_distance, whitened, final_shannon, timestamp))
+}
+
+fn read_hlth(content: &[u8]) -> Vec<f64> {
+    let mut scores = Vec::new();
+    let Some(count_bytes) = content.get(0..4) else {
+        return scores;
+    };
+    let count = u32::from_be_bytes(count_bytes.try_into().unwrap()) as usize;
+    let mut pos = 4;
+    for _ in 0..count {
+        let Some(chunk) = content.get(pos..pos + 8) else {
+            break;
+        };
+        scores.push(f64::from_be_bytes(chunk.try_into().unwrap()));
+        pos += 8;
+    }
+    scores
+}
+
+/// Parses an `OCEL` container produced by [`write_container`]. Box tags it
+/// doesn't recognize are skipped forward by their size (handled by
+/// `iter_boxes` itself), so containers can grow new box types without
+/// breaking older readers.
+pub fn read_container(buf: &[u8]) -> Option<Container> {
+    let root = iter_boxes(buf).find(|b| b.tag == TAG_ROOT)?;
+
+    let mut meta_fields = None;
+    let mut chunk_shannon = Vec::new();
+    let mut data = None;
+
+    for b in iter_boxes(root.content) {
+        if b.tag == TAG_META {
+            meta_fields = read_meta(b.content);
+        } else if b.tag == TAG_HLTH {
+            chunk_shannon = read_hlth(b.content);
+        } else if b.tag == TAG_DATA {
+            data = Some(b.content.to_vec());
+        }
+    }
+
+    let (method, width, height, minimum_distance, whitened, final_shannon, timestamp) = meta_fields?;
+
+    Some(Container {
+        meta: Meta {
+            method,
+            width,
+            height,
+            minimum_distance,
+            whitened,
+            chunk_shannon,
+            final_shannon,
+            timestamp,
+        },
+        data: data?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_meta_and_data() {
+        let meta = Meta::now(Method::ChopAndTack, 1920, 1080, 20, true, vec![4.5, 4.8], 4.9);
+        let data = b"some entropy bytes".to_vec();
+
+        let bytes = write_container(&meta, &data);
+        let parsed = read_container(&bytes).expect("container should parse");
+
+        assert_eq!(parsed.meta.method, Method::ChopAndTack);
+        assert_eq!(parsed.meta.width, 1920);
+        assert_eq!(parsed.meta.height, 1080);
+        assert_eq!(parsed.meta.minimum_distance, 20);
+        assert!(parsed.meta.whitened);
+        assert_eq!(parsed.meta.chunk_shannon, vec![4.5, 4.8]);
+        assert_eq!(parsed.meta.final_shannon, 4.9);
+        assert_eq!(parsed.data, data);
+    }
+
+    #[test]
+    fn skips_unknown_box_tags() {
+        let meta = Meta::now(Method::PickAndFlip, 640, 480, 0, false, vec![], 7.9);
+        let mut bytes = write_container(&meta, b"abc");
+
+        // Splice an unknown box in right after the OCEL header so forward
+        // compatibility can be checked: a reader built against this format
+        // should skip it rather than choke on it.
+        let mut unknown_box = Vec::new();
+        unknown_box.extend_from_slice(&12u32.to_be_bytes());
+        unknown_box.extend_from_slice(b"UNKN");
+        unknown_box.extend_from_slice(b"xxxx");
+        bytes.splice(8..8, unknown_box);
+        let total_len = bytes.len() as u32;
+        bytes[0..4].copy_from_slice(&total_len.to_be_bytes());
+
+        let parsed = read_container(&bytes).expect("container with an unknown box should still parse");
+        assert_eq!(parsed.data, b"abc");
+    }
+
+    #[test]
+    fn rejects_truncated_meta_name() {
+        // A META box whose declared name length overruns its own content
+        // must not panic.
+        let mut content = Vec::new();
+        content.extend_from_slice(&0xFFFF_FFFFu32.to_be_bytes());
+        assert!(read_meta(&content).is_none());
+    }
+
+    #[test]
+    fn rejects_garbage_input() {
+        assert!(read_container(b"not a container").is_none());
+        assert!(read_container(&[]).is_none());
+    }
+}
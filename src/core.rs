@@ -1,20 +1,10 @@
 use std::collections::{HashMap, HashSet};
 
+use crate::bits::{pack_bits_truncating, unpack_bits, BitOrder};
+
 pub struct Ocelli;
 
 impl Ocelli {
-    fn bits_to_bytes(&self, bits: &[u8]) -> Vec<u8> {
-        bits.chunks(8)
-            .filter_map(|chunk| {
-                if chunk.len() == 8 {
-                    Some(chunk.iter().fold(0, |byte, &bit| (byte << 1) | bit))
-                } else {
-                    None
-                }
-            })
-            .collect()
-    }
-
     pub fn chop_and_tack(
     // Extracts entropy from two frames by comparing pixel values in a specific grid pattern.
     // The resulting entropy is constructed by appending 1s or 0s based on pixel differences.
@@ -25,6 +15,7 @@ impl Ocelli {
         previous: &[u8],
         width: usize,
         minimum_distance: usize,
+        order: BitOrder,
     ) -> Option<Vec<u8>> {
         let height = current.len() / width;
         if width <= 200 || height <= 200 || current.len() != previous.len() {
@@ -55,13 +46,16 @@ impl Ocelli {
             );
         }
 
-        Some(self.bits_to_bytes(&entropy))
+        // The pixel-difference and bias filters above produce an arbitrary bit
+        // count, so a trailing partial byte must be dropped rather than
+        // zero-padded — this entropy is often written out un-whitened.
+        Some(pack_bits_truncating(&entropy, order))
     }
 
-    pub fn pick_and_flip(&self, data: &[u8], low: u8, high: u8, current_frame_index: usize) -> Vec<u8> {
+    pub fn pick_and_flip(&self, data: &[u8], low: u8, high: u8, current_frame_index: usize, order: BitOrder) -> Vec<u8> {
     // Extracts the least significant bit (LSB) of each pixel brightness, flipping it based on the frame index.
     // Generates entropy by combining these bits into bytes.
-    // Algorithm is a simplified version of R. Li, "A True Random Number Generator algorithm from 
+    // Algorithm is a simplified version of R. Li, "A True Random Number Generator algorithm from
     // digital camera image noise for varying lighting conditions," doi: 10.1109/SECON.2015.7132901.
 
         let mut bits = Vec::with_capacity(data.len());
@@ -74,7 +68,9 @@ impl Ocelli {
                 bits.push(lsb);
             }
         }
-        self.bits_to_bytes(&bits)
+        // Same reasoning as `chop_and_tack`: the `(low..=high)` filter yields
+        // an arbitrary bit count, so drop rather than zero-pad the remainder.
+        pack_bits_truncating(&bits, order)
     }
 
     pub fn shannon(&self, data: &[u8]) -> f64 {
@@ -95,31 +91,22 @@ impl Ocelli {
         })
     }
 
-    pub fn whiten(&self, entropy: &[u8]) -> Vec<u8> {
+    pub fn whiten(&self, entropy: &[u8], order: BitOrder) -> Vec<u8> {
     // Applies von Neumann whitening to reduce bias in the input entropy.
     // Pairs of bits are analyzed, and only unbiased pairs are used to construct the output.
 
-        let mut out = Vec::with_capacity(entropy.len() / 2);
-        let mut current_byte = 0u8;
-        let mut bit_count = 0;
-
-        for &byte in entropy {
-            for i in (0..8).step_by(2) {
-                let bit1 = (byte >> (7 - i)) & 1;
-                let bit2 = (byte >> (6 - i)) & 1;
-                match (bit1, bit2) {
-                    (0, 1) => { current_byte = (current_byte << 1) | 0; bit_count += 1; }
-                    (1, 0) => { current_byte = (current_byte << 1) | 1; bit_count += 1; }
-                    _ => {}
-                }
-                if bit_count == 8 {
-                    out.push(current_byte);
-                    current_byte = 0;
-                    bit_count = 0;
-                }
+        let bits = unpack_bits(entropy, order);
+        let mut out_bits = Vec::with_capacity(bits.len() / 2);
+
+        for pair in bits.chunks_exact(2) {
+            match (pair[0], pair[1]) {
+                (0, 1) => out_bits.push(0),
+                (1, 0) => out_bits.push(1),
+                _ => {} // discard (0,0) and (1,1) pairs
             }
         }
-        out
+
+        pack_bits_truncating(&out_bits, order)
     }
 
     pub fn is_covered(&self, grayscale: &[u8], threshold: usize) -> bool {
@@ -1,7 +1,18 @@
 use std::slice;
 
+pub mod capture;
+mod bits;
+pub mod container;
 mod core;
-use crate::core::Ocelli;
+mod reader;
+pub use crate::bits::BitOrder;
+pub use crate::core::Ocelli;
+use crate::container::Method;
+use crate::reader::{ByteOrder, EntropyReader};
+
+fn bit_order_from_u8(order: u8) -> BitOrder {
+    if order == 0 { BitOrder::Msb } else { BitOrder::Lsb }
+}
 
 #[no_mangle]
 pub extern "C" fn chop_and_tack(
@@ -11,6 +22,7 @@ pub extern "C" fn chop_and_tack(
     previous_len: usize,
     width: usize,
     minimum_distance: usize,
+    bit_order: u8, // 0 = MSB-first, 1 = LSB-first
     result_ptr: *mut u8,   // out buffer (caller-allocated)
     result_len: *mut usize // out: actual bytes written
 ) {
@@ -22,7 +34,7 @@ pub extern "C" fn chop_and_tack(
     let previous = unsafe { slice::from_raw_parts(previous_ptr, previous_len) };
 
     let ocelli = Ocelli;
-    let result = match ocelli.chop_and_tack(current, previous, width, minimum_distance) {
+    let result = match ocelli.chop_and_tack(current, previous, width, minimum_distance, bit_order_from_u8(bit_order)) {
         Some(v) => v,
         None => {
             unsafe { *result_len = 0; }
@@ -44,6 +56,7 @@ pub extern "C" fn pick_and_flip(
     low: u8,
     high: u8,
     current_frame_index: usize,
+    bit_order: u8, // 0 = MSB-first, 1 = LSB-first
     result_ptr: *mut u8,   // out buffer (caller-allocated)
     result_len: *mut usize // out: actual bytes written
 ) {
@@ -57,7 +70,7 @@ pub extern "C" fn pick_and_flip(
 
     let data = unsafe { slice::from_raw_parts(data_ptr, data_len) };
     let ocelli = Ocelli;
-    let result = ocelli.pick_and_flip(data, low, high, current_frame_index);
+    let result = ocelli.pick_and_flip(data, low, high, current_frame_index, bit_order_from_u8(bit_order));
 
     unsafe {
         let out = slice::from_raw_parts_mut(result_ptr, result.len());
@@ -83,6 +96,7 @@ pub extern "C" fn shannon(
 pub extern "C" fn whiten(
     entropy_ptr: *const u8,
     entropy_len: usize,
+    bit_order: u8, // 0 = MSB-first, 1 = LSB-first
     result_ptr: *mut u8,   // out buffer (caller-allocated)
     result_len: *mut usize // out: actual bytes written
 ) {
@@ -92,7 +106,7 @@ pub extern "C" fn whiten(
 
     let entropy = unsafe { slice::from_raw_parts(entropy_ptr, entropy_len) };
     let ocelli = Ocelli;
-    let result = ocelli.whiten(entropy);
+    let result = ocelli.whiten(entropy, bit_order_from_u8(bit_order));
 
     unsafe {
         let out = slice::from_raw_parts_mut(result_ptr, result.len());
@@ -114,3 +128,209 @@ pub extern "C" fn is_covered(
     let ocelli = Ocelli;
     ocelli.is_covered(grayscale, threshold)
 }
+
+#[no_mangle]
+pub extern "C" fn write_container(
+    method: u8, // 0 = chop_and_tack, 1 = pick_and_flip
+    width: u32,
+    height: u32,
+    minimum_distance: u32,
+    whitened: bool,
+    chunk_shannon_ptr: *const f64,
+    chunk_shannon_len: usize,
+    final_shannon: f64,
+    data_ptr: *const u8,
+    data_len: usize,
+    result_ptr: *mut u8,   // out buffer (caller-allocated)
+    result_len: *mut usize // out: actual bytes written
+) {
+    if result_ptr.is_null() || result_len.is_null() {
+        return;
+    }
+    if (chunk_shannon_len > 0 && chunk_shannon_ptr.is_null()) || (data_len > 0 && data_ptr.is_null()) {
+        unsafe { *result_len = 0; }
+        return;
+    }
+
+    let method = match method {
+        0 => Method::ChopAndTack,
+        1 => Method::PickAndFlip,
+        _ => {
+            unsafe { *result_len = 0; }
+            return;
+        }
+    };
+
+    let chunk_shannon = unsafe { slice::from_raw_parts(chunk_shannon_ptr, chunk_shannon_len) }.to_vec();
+    let data = unsafe { slice::from_raw_parts(data_ptr, data_len) };
+
+    let meta = container::Meta::now(method, width, height, minimum_distance, whitened, chunk_shannon, final_shannon);
+    let result = container::write_container(&meta, data);
+
+    unsafe {
+        let out = slice::from_raw_parts_mut(result_ptr, result.len());
+        out.copy_from_slice(&result);
+        *result_len = result.len();
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn read_container(
+    container_ptr: *const u8,
+    container_len: usize,
+    method_out: *mut u8,
+    width_out: *mut u32,
+    height_out: *mut u32,
+    minimum_distance_out: *mut u32,
+    whitened_out: *mut bool,
+    final_shannon_out: *mut f64,
+    timestamp_out: *mut u64,
+    chunk_shannon_ptr: *mut f64, // out buffer (caller-allocated)
+    chunk_shannon_len: *mut usize, // in: capacity, out: actual count written
+    data_ptr: *mut u8,     // out buffer (caller-allocated)
+    data_len: *mut usize   // in: capacity, out: actual bytes written
+) -> bool {
+    if container_ptr.is_null()
+        || method_out.is_null()
+        || width_out.is_null()
+        || height_out.is_null()
+        || minimum_distance_out.is_null()
+        || whitened_out.is_null()
+        || final_shannon_out.is_null()
+        || timestamp_out.is_null()
+        || chunk_shannon_len.is_null()
+        || data_len.is_null()
+    {
+        return false;
+    }
+
+    let buf = unsafe { slice::from_raw_parts(container_ptr, container_len) };
+    let parsed = match container::read_container(buf) {
+        Some(c) => c,
+        None => return false,
+    };
+
+    let chunk_capacity = unsafe { *chunk_shannon_len };
+    let data_capacity = unsafe { *data_len };
+    if parsed.meta.chunk_shannon.len() > chunk_capacity || parsed.data.len() > data_capacity {
+        return false;
+    }
+
+    unsafe {
+        *method_out = match parsed.meta.method {
+            Method::ChopAndTack => 0,
+            Method::PickAndFlip => 1,
+        };
+        *width_out = parsed.meta.width;
+        *height_out = parsed.meta.height;
+        *minimum_distance_out = parsed.meta.minimum_distance;
+        *whitened_out = parsed.meta.whitened;
+        *final_shannon_out = parsed.meta.final_shannon;
+        *timestamp_out = parsed.meta.timestamp;
+
+        if !parsed.meta.chunk_shannon.is_empty() {
+            let out = slice::from_raw_parts_mut(chunk_shannon_ptr, parsed.meta.chunk_shannon.len());
+            out.copy_from_slice(&parsed.meta.chunk_shannon);
+        }
+        *chunk_shannon_len = parsed.meta.chunk_shannon.len();
+
+        if !parsed.data.is_empty() {
+            let out = slice::from_raw_parts_mut(data_ptr, parsed.data.len());
+            out.copy_from_slice(&parsed.data);
+        }
+        *data_len = parsed.data.len();
+    }
+
+    true
+}
+
+#[no_mangle]
+pub extern "C" fn entropy_reader_new(
+    data_ptr: *const u8,
+    data_len: usize,
+    big_endian: bool,
+) -> *mut EntropyReader {
+    if data_ptr.is_null() {
+        return std::ptr::null_mut();
+    }
+    let data = unsafe { slice::from_raw_parts(data_ptr, data_len) }.to_vec();
+    let order = if big_endian { ByteOrder::Big } else { ByteOrder::Little };
+    Box::into_raw(Box::new(EntropyReader::new(data, order)))
+}
+
+#[no_mangle]
+pub extern "C" fn entropy_reader_free(reader_ptr: *mut EntropyReader) {
+    if reader_ptr.is_null() {
+        return;
+    }
+    unsafe { drop(Box::from_raw(reader_ptr)); }
+}
+
+#[no_mangle]
+pub extern "C" fn entropy_reader_next_u16(reader_ptr: *mut EntropyReader, result_out: *mut u16) -> bool {
+    if reader_ptr.is_null() || result_out.is_null() {
+        return false;
+    }
+    let reader = unsafe { &mut *reader_ptr };
+    match reader.next_u16() {
+        Some(v) => { unsafe { *result_out = v; } true }
+        None => false,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn entropy_reader_next_u32(reader_ptr: *mut EntropyReader, result_out: *mut u32) -> bool {
+    if reader_ptr.is_null() || result_out.is_null() {
+        return false;
+    }
+    let reader = unsafe { &mut *reader_ptr };
+    match reader.next_u32() {
+        Some(v) => { unsafe { *result_out = v; } true }
+        None => false,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn entropy_reader_next_u64(reader_ptr: *mut EntropyReader, result_out: *mut u64) -> bool {
+    if reader_ptr.is_null() || result_out.is_null() {
+        return false;
+    }
+    let reader = unsafe { &mut *reader_ptr };
+    match reader.next_u64() {
+        Some(v) => { unsafe { *result_out = v; } true }
+        None => false,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn entropy_reader_uniform_below(
+    reader_ptr: *mut EntropyReader,
+    bound: u64,
+    result_out: *mut u64,
+) -> bool {
+    if reader_ptr.is_null() || result_out.is_null() {
+        return false;
+    }
+    let reader = unsafe { &mut *reader_ptr };
+    match reader.uniform_below(bound) {
+        Some(v) => { unsafe { *result_out = v; } true }
+        None => false,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn entropy_reader_uniform_range(
+    reader_ptr: *mut EntropyReader,
+    low: u64,
+    high: u64,
+    result_out: *mut u64,
+) -> bool {
+    if reader_ptr.is_null() || result_out.is_null() {
+        return false;
+    }
+    let reader = unsafe { &mut *reader_ptr };
+    match reader.uniform_range(low, high) {
+        Some(v) => { unsafe { *result_out = v; } true }
+        None => false,
+    }
+}
@@ -0,0 +1,146 @@
+// Consuming reader over an entropy pool, yielding typed integers and
+// unbiased bounded samples instead of raw bytes.
+
+/// Byte order used when assembling a pulled byte run into an integer,
+/// following the `from_be_bytes`/`from_le_bytes` split on the primitive
+/// integer types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    Big,
+    Little,
+}
+
+/// A one-shot consumer over an entropy pool: each `next_*` call removes the
+/// bytes it used, so the same bytes are never handed out twice.
+pub struct EntropyReader {
+    buf: Vec<u8>,
+    pos: usize,
+    order: ByteOrder,
+}
+
+impl EntropyReader {
+    pub fn new(buf: Vec<u8>, order: ByteOrder) -> Self {
+        EntropyReader { buf, pos: 0, order }
+    }
+
+    /// Bytes left in the pool.
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    fn take(&mut self, n: usize) -> Option<&[u8]> {
+        if self.pos + n > self.buf.len() {
+            return None;
+        }
+        let slice = &self.buf[self.pos..self.pos + n];
+        self.pos += n;
+        Some(slice)
+    }
+
+    pub fn next_u16(&mut self) -> Option<u16> {
+        let bytes: [u8; 2] = self.take(2)?.try_into().unwrap();
+        Some(match self.order {
+            ByteOrder::Big => u16::from_be_bytes(bytes),
+            ByteOrder::Little => u16::from_le_bytes(bytes),
+        })
+    }
+
+    pub fn next_u32(&mut self) -> Option<u32> {
+        let bytes: [u8; 4] = self.take(4)?.try_into().unwrap();
+        Some(match self.order {
+            ByteOrder::Big => u32::from_be_bytes(bytes),
+            ByteOrder::Little => u32::from_le_bytes(bytes),
+        })
+    }
+
+    pub fn next_u64(&mut self) -> Option<u64> {
+        let bytes: [u8; 8] = self.take(8)?.try_into().unwrap();
+        Some(match self.order {
+            ByteOrder::Big => u64::from_be_bytes(bytes),
+            ByteOrder::Little => u64::from_le_bytes(bytes),
+        })
+    }
+
+    /// Draws a value uniformly distributed over `[0, bound)`.
+    ///
+    /// Uses rejection sampling against the largest multiple of `bound` that
+    /// fits in a `u64` so the result isn't biased the way `next_u64() % bound`
+    /// would be for a `bound` that doesn't evenly divide 2^64.
+    pub fn uniform_below(&mut self, bound: u64) -> Option<u64> {
+        if bound == 0 {
+            return None;
+        }
+        let limit = u64::MAX - (u64::MAX % bound);
+        loop {
+            let draw = self.next_u64()?;
+            if draw < limit {
+                return Some(draw % bound);
+            }
+        }
+    }
+
+    /// Draws a value uniformly distributed over `[low, high)`.
+    pub fn uniform_range(&mut self, low: u64, high: u64) -> Option<u64> {
+        if low >= high {
+            return None;
+        }
+        Some(low + self.uniform_below(high - low)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_big_and_little_endian() {
+        let mut be = EntropyReader::new(vec![0x01, 0x02, 0x03, 0x04], ByteOrder::Big);
+        assert_eq!(be.next_u32(), Some(0x01020304));
+
+        let mut le = EntropyReader::new(vec![0x01, 0x02, 0x03, 0x04], ByteOrder::Little);
+        assert_eq!(le.next_u32(), Some(0x04030201));
+    }
+
+    #[test]
+    fn consumes_bytes_across_calls() {
+        let mut reader = EntropyReader::new(vec![0xAA, 0xBB, 0x01, 0x02], ByteOrder::Big);
+        assert_eq!(reader.next_u16(), Some(0xAABB));
+        assert_eq!(reader.next_u16(), Some(0x0102));
+        assert_eq!(reader.remaining(), 0);
+    }
+
+    #[test]
+    fn exhausted_pool_returns_none() {
+        let mut reader = EntropyReader::new(vec![0x01, 0x02], ByteOrder::Big);
+        assert_eq!(reader.next_u32(), None);
+        // A failed read must not consume bytes from the pool.
+        assert_eq!(reader.remaining(), 2);
+    }
+
+    #[test]
+    fn uniform_below_stays_in_range() {
+        // 0xFF bytes would make every draw u64::MAX, which is always rejected.
+        let mut reader = EntropyReader::new(vec![0xFE; 800], ByteOrder::Big);
+        for _ in 0..10 {
+            let v = reader.uniform_below(7).expect("pool should have enough bytes");
+            assert!(v < 7);
+        }
+    }
+
+    #[test]
+    fn uniform_range_stays_in_bounds() {
+        let mut reader = EntropyReader::new(vec![0x42; 800], ByteOrder::Little);
+        for _ in 0..10 {
+            let v = reader.uniform_range(10, 20).expect("pool should have enough bytes");
+            assert!((10..20).contains(&v));
+        }
+    }
+
+    #[test]
+    fn rejects_empty_and_inverted_ranges() {
+        let mut reader = EntropyReader::new(vec![0x01; 64], ByteOrder::Big);
+        assert_eq!(reader.uniform_below(0), None);
+        assert_eq!(reader.uniform_range(5, 5), None);
+        assert_eq!(reader.uniform_range(5, 1), None);
+    }
+}